@@ -5,23 +5,293 @@ use wasm_bindgen_futures::JsFuture; // Для работы с асинхронн
 #[cfg(target_arch = "wasm32")]
 use web_sys::{WebTransport, WebTransportDatagramDuplexStream}; // WebTransport для WASM.
 #[cfg(not(target_arch = "wasm32"))]
-use quinn::{ClientConfig, Endpoint, ServerConfig}; // QUIC для нативных приложений.
+use quinn::{ClientConfig, Connection, Endpoint, ServerConfig}; // QUIC для нативных приложений.
 use tokio_tungstenite::{connect_async, accept_async, tungstenite::protocol::Message}; // WebSocket.
 use tokio::net::TcpListener; // Для прослушивания TCP-соединений.
 use tokio::sync::RwLock; // Асинхронная блокировка для безопасного доступа.
-use futures_util::{StreamExt, SinkExt}; // Для работы с потоками и сокетами.
+use futures_util::{StreamExt, SinkExt, Stream, Sink}; // Для работы с потоками и сокетами.
+use futures_util::stream::{SplitSink, SplitStream}; // Раздельные read/write половины WebSocket-сокета.
 use url::Url; // Для парсинга URL.
 use std::sync::Arc; // Для многопоточного доступа к данным.
+use std::pin::Pin; // Для закрепления самоссылающихся futures/stream адаптеров.
+use std::task::{Context, Poll}; // Для ручной реализации Stream/Sink у Framed.
+use std::sync::Mutex; // Синхронный мьютекс для метки времени последней активности.
+use std::time::{Duration, Instant}; // Для таймингов heartbeat у WebSocket.
 use webrtc::api::APIBuilder; // Для создания WebRTC API.
 use webrtc::peer_connection::{RTCConfiguration, RTCPeerConnection}; // Для работы с соединениями WebRTC.
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription; // SDP offer/answer.
+use webrtc::ice_transport::ice_candidate::RTCIceCandidateInit; // ICE-кандидат для трикла.
 use webrtc::data_channel::{DataChannel, DataChannelMessage}; // Для работы с каналами данных WebRTC.
+use serde::{Deserialize, Serialize}; // Для (де)сериализации сигнальных сообщений.
+use tokio_tungstenite::Connector; // Выбор TLS/обычного коннектора для WebSocket.
+use tokio_rustls::TlsAcceptor; // Терминация TLS на серверной стороне WebSocket.
+use std::collections::VecDeque; // Буфер неотправленных кадров у ReconnectingTransport.
+use tokio::sync::Mutex as AsyncMutex; // Async-мьютекс для буфера, который удерживается через .await.
+use rand::Rng; // Для джиттера между попытками переподключения.
 
+// Типизированное сообщение транспортного уровня вместо плоского `Vec<u8>`:
+// позволяет отличать текст от бинарных данных и видеть control-фреймы (ping/pong/close).
+#[derive(Debug, Clone, PartialEq)]
+enum TransportMessage {
+    Text(String),
+    Binary(Vec<u8>),
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    Close,
+}
+
+// Настройки keepalive для WebSocket-соединения: как часто слать Ping и сколько
+// ждать любого входящего трафика, прежде чем считать соединение мертвым.
+#[derive(Debug, Clone)]
+struct WebSocketConfig {
+    ping_interval: Duration,
+    pong_timeout: Duration,
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        WebSocketConfig {
+            ping_interval: Duration::from_secs(30),
+            pong_timeout: Duration::from_secs(90),
+        }
+    }
+}
+
+// Единые настройки TLS для всех нативных транспортов: корневые сертификаты,
+// опциональный клиентский/серверный сертификат+ключ, dev-флаг отключения проверки
+// и список ALPN-протоколов. Пустой `root_certs` означает "доверять системным корням".
+#[derive(Clone, Default)]
+struct TlsConfig {
+    root_certs: Vec<Vec<u8>>, // DER-кодированные корневые сертификаты.
+    identity: Option<(Vec<u8>, Vec<u8>)>, // (цепочка сертификатов DER, приватный ключ DER) — клиентский cert или identity сервера.
+    insecure_skip_verify: bool, // Отключить проверку сертификата сервера (только для разработки).
+    alpn_protocols: Vec<Vec<u8>>,
+}
+
+impl TlsConfig {
+    // Набор корневых сертификатов: либо явно заданные, либо системные (rustls-native-certs).
+    fn root_store(&self) -> Result<rustls::RootCertStore, Box<dyn std::error::Error>> {
+        let mut roots = rustls::RootCertStore::empty();
+        if self.root_certs.is_empty() {
+            for cert in rustls_native_certs::load_native_certs()? {
+                roots.add(&rustls::Certificate(cert.0))?;
+            }
+        } else {
+            for der in &self.root_certs {
+                roots.add(&rustls::Certificate(der.clone()))?;
+            }
+        }
+        Ok(roots)
+    }
+
+    // Собирает `rustls::ClientConfig` для клиентской стороны (Quinn и WebSocket).
+    fn client_rustls_config(&self) -> Result<rustls::ClientConfig, Box<dyn std::error::Error>> {
+        let builder = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(self.root_store()?);
+        let mut config = match &self.identity {
+            Some((chain, key)) => builder
+                .with_client_auth_cert(vec![rustls::Certificate(chain.clone())], rustls::PrivateKey(key.clone()))?,
+            None => builder.with_no_client_auth(),
+        };
+        if self.insecure_skip_verify {
+            // Dev-режим: принимаем любой сертификат сервера (самоподписанные, без доверенного CA).
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(InsecureServerCertVerifier));
+        }
+        config.alpn_protocols = self.alpn_protocols.clone();
+        Ok(config)
+    }
+
+    // Собирает `rustls::ServerConfig` для серверной стороны (Quinn и WebSocket).
+    fn server_rustls_config(&self) -> Result<rustls::ServerConfig, Box<dyn std::error::Error>> {
+        let (chain, key) = self
+            .identity
+            .clone()
+            .ok_or("server TLS requires an identity certificate and private key")?;
+        let mut config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(vec![rustls::Certificate(chain)], rustls::PrivateKey(key))?;
+        config.alpn_protocols = self.alpn_protocols.clone();
+        Ok(config)
+    }
+}
+
+// Верификатор сертификатов для `insecure_skip_verify`: принимает любой сертификат.
+// Использовать только для локальной разработки с самоподписанными сертификатами.
+struct InsecureServerCertVerifier;
+
+impl rustls::client::ServerCertVerifier for InsecureServerCertVerifier {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: std::time::SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+// `WebSocketStream<S>` реализует один и тот же `Stream`/`Sink` интерфейс независимо
+// от конкретного типа нижележащего IO `S` (обычный TCP или TLS-обертка над ним).
+// Поэтому вместо того чтобы хранить `S` за `dyn`, прячем сам `WebSocketStream<S>` —
+// это позволяет `WebSocketClient` одинаково работать с `ws://` и `wss://`, клиентом и сервером.
+trait WsIo:
+    Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>>
+    + Sink<Message, Error = tokio_tungstenite::tungstenite::Error>
+    + Send
+    + Unpin
+{
+}
+impl<T> WsIo for T where
+    T: Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>>
+        + Sink<Message, Error = tokio_tungstenite::tungstenite::Error>
+        + Send
+        + Unpin
+{
+}
+
+// Сообщения сигнального обмена для WebRTC: SDP offer/answer и трикл ICE-кандидатов,
+// переданные как JSON поверх обычного `WebSocketClient`.
+#[derive(Debug, Serialize, Deserialize)]
+enum SignalingMessage {
+    Offer(RTCSessionDescription),
+    Answer(RTCSessionDescription),
+    Candidate(RTCIceCandidateInit),
+}
+
+// Объектно-безопасная часть трейта: только то, что нужно, когда соединение уже установлено.
+// Конструирование (connect/listen) вынесено в свободные функции-диспетчеры ниже, так как
+// они возвращают конкретный тип и не могут быть частью dyn-трейта.
 #[async_trait]
 trait Transport {
-    async fn connect(addr: &str) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized;
     async fn send(&self, data: &[u8]) -> Result<(), Box<dyn std::error::Error>>;
     async fn receive(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>>;
-    async fn listen(addr: &str) -> Result<Self, Box<dyn std::error::Error>> where Self: Sized;
+    async fn receive_message(&self) -> Result<TransportMessage, Box<dyn std::error::Error>>;
+    async fn close(&self) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+// Диспетчер по схеме URL: парсит `url` и создает подключение нужного транспорта,
+// возвращая его за единым типом `Box<dyn Transport>`.
+async fn connect(url: &str) -> Result<Box<dyn Transport>, Box<dyn std::error::Error>> {
+    let parsed = Url::parse(url)?;
+    match parsed.scheme() {
+        "ws" | "wss" => Ok(Box::new(WebSocketClient::connect(url).await?)),
+        #[cfg(not(target_arch = "wasm32"))]
+        "quic" => Ok(Box::new(QuinnClient::connect(&quic_authority(&parsed)?).await?)),
+        "webrtc" => Ok(Box::new(WebRTCClient::connect(&webrtc_signaling_url(&parsed)?).await?)),
+        #[cfg(target_arch = "wasm32")]
+        "webtransport" | "https" => Ok(Box::new(WebTransportClient::connect(url).await?)),
+        scheme => Err(format!("unsupported transport scheme: {}", scheme).into()),
+    }
+}
+
+// Диспетчер по схеме URL для серверной стороны (см. `connect`).
+async fn listen(url: &str) -> Result<Box<dyn Transport>, Box<dyn std::error::Error>> {
+    let parsed = Url::parse(url)?;
+    match parsed.scheme() {
+        "ws" => Ok(Box::new(
+            WebSocketClient::listen_with(&ws_authority(&parsed)?, WebSocketConfig::default(), None).await?,
+        )),
+        "wss" => Ok(Box::new(
+            WebSocketClient::listen_with(
+                &ws_authority(&parsed)?,
+                WebSocketConfig::default(),
+                Some(TlsConfig::default()),
+            )
+            .await?,
+        )),
+        #[cfg(not(target_arch = "wasm32"))]
+        "quic" => Ok(Box::new(QuinnClient::listen(&quic_authority(&parsed)?).await?)),
+        "webrtc" => Ok(Box::new(WebRTCClient::listen(&ws_authority(&parsed)?).await?)),
+        #[cfg(target_arch = "wasm32")]
+        "webtransport" | "https" => Ok(Box::new(WebTransportClient::listen(url).await?)),
+        scheme => Err(format!("unsupported transport scheme: {}", scheme).into()),
+    }
+}
+
+// `webrtc://host:port` описывает сам P2P-транспорт, но устанавливается он через
+// сигнальный WebSocket — переписываем схему на `ws` для `WebRTCClient::connect`.
+fn webrtc_signaling_url(url: &Url) -> Result<String, Box<dyn std::error::Error>> {
+    // `Url::set_scheme` не переключает неспециальную схему (`webrtc`) на специальную (`ws`),
+    // поэтому заменяем префикс схемы в строке напрямую.
+    Ok(format!("ws{}", &url.as_str()[url.scheme().len()..]))
+}
+
+// Извлекает "host:port" из URL для транспортов, которым нужен голый socket-адрес (QUIC).
+#[cfg(not(target_arch = "wasm32"))]
+fn quic_authority(url: &Url) -> Result<String, Box<dyn std::error::Error>> {
+    let host = url.host_str().ok_or("quic:// url is missing a host")?;
+    let port = url.port().ok_or("quic:// url is missing a port")?;
+    Ok(format!("{}:{}", host, port))
+}
+
+// Извлекает "host:port" из URL для WebSocket-сервера (слушает как обычный TCP-листенер).
+fn ws_authority(url: &Url) -> Result<String, Box<dyn std::error::Error>> {
+    let host = url.host_str().ok_or("ws:// url is missing a host")?;
+    let port = url.port().ok_or("ws:// url is missing a port")?;
+    Ok(format!("{}:{}", host, port))
+}
+
+// Представляет любой транспорт как единый `Stream` + `Sink`, по образцу
+// async-tungstenite, где сокет "is just a stream of messages coming in and going out".
+// Это позволяет гонять транспорт через `select!` и комбинаторы futures_util,
+// например `stream.forward(sink)`.
+struct Framed {
+    stream: Pin<Box<dyn Stream<Item = Result<Vec<u8>, Box<dyn std::error::Error>>> + Send>>,
+    sink: Pin<Box<dyn Sink<Vec<u8>, Error = Box<dyn std::error::Error>> + Send>>,
+}
+
+impl Stream for Framed {
+    type Item = Result<Vec<u8>, Box<dyn std::error::Error>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+impl Sink<Vec<u8>> for Framed {
+    type Error = Box<dyn std::error::Error>;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.as_mut().poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: Vec<u8>) -> Result<(), Self::Error> {
+        self.sink.as_mut().start_send(item)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.as_mut().poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.sink.as_mut().poll_close(cx)
+    }
+}
+
+// Небольшая обертка Stream/Sink поверх send/receive для транспортов без
+// собственного раздельного read/write-потока (QUIC, WebRTC): каждый poll
+// просто вызывает `receive`/`send` на общем `Arc<T>`.
+fn shim_framed<T: Transport + Send + Sync + 'static>(transport: T) -> Framed {
+    let transport = Arc::new(transport);
+    let recv_transport = transport.clone();
+    let stream = futures_util::stream::unfold(recv_transport, |t| async move {
+        Some((t.receive().await, t))
+    });
+    let sink = futures_util::sink::unfold(transport, |t, data: Vec<u8>| async move {
+        t.send(&data).await?;
+        Ok::<_, Box<dyn std::error::Error>>(t)
+    });
+    Framed {
+        stream: Box::pin(stream),
+        sink: Box::pin(sink),
+    }
 }
 
 // WebTransportClient для работы в WebAssembly (браузер).
@@ -31,13 +301,21 @@ struct WebTransportClient {
 }
 
 #[cfg(target_arch = "wasm32")]
-#[async_trait]
-impl Transport for WebTransportClient {
+impl WebTransportClient {
     async fn connect(addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let transport = WebTransport::new(&JsValue::from_str(addr))?; // Подключаем WebTransport.
         Ok(WebTransportClient { transport })
     }
 
+    async fn listen(_addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        // WebTransport не поддерживает слушание серверов в текущей реализации.
+        Err("Listening is not supported for WebTransport".into())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[async_trait]
+impl Transport for WebTransportClient {
     async fn send(&self, _data: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
         // В WebTransport пока нет реализации отправки данных в примере.
         Ok(())
@@ -45,12 +323,17 @@ impl Transport for WebTransportClient {
 
     async fn receive(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         // В WebTransport пока нет реализации получения данных.
-        Ok(vec![]) 
+        Ok(vec![])
     }
 
-    async fn listen(_addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        // WebTransport не поддерживает слушание серверов в текущей реализации.
-        Err("Listening is not supported for WebTransport".into())
+    async fn receive_message(&self) -> Result<TransportMessage, Box<dyn std::error::Error>> {
+        // WebTransport работает с датаграммами — трактуем их как бинарные сообщения.
+        Ok(TransportMessage::Binary(self.receive().await?))
+    }
+
+    async fn close(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // В WebTransport пока нет реализации закрытия соединения в примере.
+        Ok(())
     }
 }
 
@@ -58,60 +341,264 @@ impl Transport for WebTransportClient {
 #[cfg(not(target_arch = "wasm32"))]
 struct QuinnClient {
     endpoint: Endpoint, // Точка подключения для QUIC.
+    connection: Connection, // Установленное QUIC-соединение.
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-#[async_trait]
-impl Transport for QuinnClient {
+impl QuinnClient {
+    // Отправляет кадр с префиксом длины (u32 big-endian) поверх bi-directional потока.
+    async fn send_framed(
+        send: &mut quinn::SendStream,
+        data: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let len = data.len() as u32;
+        send.write_all(&len.to_be_bytes()).await?;
+        send.write_all(data).await?;
+        send.finish()?;
+        Ok(())
+    }
+
+    // Читает кадр с префиксом длины (u32 big-endian) из recv-потока.
+    async fn recv_framed(
+        recv: &mut quinn::RecvStream,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let mut len_buf = [0u8; 4];
+        recv.read_exact(&mut len_buf).await?;
+        let len = u32::from_be_bytes(len_buf) as usize;
+        let mut data = vec![0u8; len];
+        recv.read_exact(&mut data).await?;
+        Ok(data)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl QuinnClient {
     async fn connect(addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        // Подключаемся к серверу через QUIC.
+        Self::connect_with_tls(addr, TlsConfig::default()).await
+    }
+
+    async fn connect_with_tls(addr: &str, tls: TlsConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        // Подключаемся к серверу через QUIC, используя переданную TLS-конфигурацию
+        // вместо ClientConfig::default() (который не соберется без провайдера крипто и сертификатов).
+        let socket_addr: SocketAddr = addr.parse()?;
+        let endpoint = Endpoint::client("0.0.0.0:0".parse()?)?;
+        let client_config = ClientConfig::new(Arc::new(tls.client_rustls_config()?));
+        let connection = endpoint
+            .connect_with(client_config, socket_addr, "localhost")?
+            .await?;
+        Ok(QuinnClient { endpoint, connection })
+    }
+
+    async fn listen(addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::listen_with_tls(addr, TlsConfig::default()).await
+    }
+
+    async fn listen_with_tls(addr: &str, tls: TlsConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        // Создаем сервер QUIC для прослушивания с заданной TLS-конфигурацией.
         let socket_addr: SocketAddr = addr.parse()?;
-        let endpoint = Endpoint::client(ClientConfig::default())?;
-        Ok(QuinnClient { endpoint })
+        let server_cfg = ServerConfig::with_crypto(Arc::new(tls.server_rustls_config()?));
+        let endpoint = Endpoint::server(server_cfg, socket_addr)?;
+        // Ждем первое входящее подключение и сохраняем его для send/receive.
+        let incoming = endpoint
+            .accept()
+            .await
+            .ok_or("QUIC endpoint closed before accepting a connection")?;
+        let connection = incoming.await?;
+        Ok(QuinnClient { endpoint, connection })
     }
+}
 
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl Transport for QuinnClient {
     async fn send(&self, data: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
-        // Отправка данных через QUIC (реализуйте логику для отправки).
-        Ok(())
+        // Открываем двунаправленный поток и отправляем кадр с префиксом длины.
+        let (mut send, _recv) = self.connection.open_bi().await?;
+        Self::send_framed(&mut send, data).await
     }
 
     async fn receive(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        // Получение данных через QUIC (реализуйте логику для получения).
-        Ok(vec![]) 
+        // Принимаем двунаправленный поток и читаем кадр с префиксом длины.
+        let (_send, mut recv) = self.connection.accept_bi().await?;
+        Self::recv_framed(&mut recv).await
     }
 
-    async fn listen(addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        // Создаем сервер QUIC для прослушивания.
-        let socket_addr: SocketAddr = addr.parse()?;
-        let server_cfg = ServerConfig::default();
-        let endpoint = Endpoint::server(server_cfg, socket_addr.into())?;
-        Ok(QuinnClient { endpoint })
+    async fn receive_message(&self) -> Result<TransportMessage, Box<dyn std::error::Error>> {
+        // QUIC в этом крейте не различает типы сообщений — все кадры бинарные.
+        Ok(TransportMessage::Binary(self.receive().await?))
+    }
+
+    async fn close(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // Закрываем QUIC-соединение и точку подключения.
+        self.connection.close(0u32.into(), b"closed");
+        self.endpoint.close(0u32.into(), b"closed");
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl QuinnClient {
+    // Представляет соединение как Stream+Sink, чтобы его можно было комбинировать
+    // с другими транспортами через futures_util (см. `Framed`).
+    fn into_framed(self) -> Framed {
+        shim_framed(self)
     }
 }
 
 // WebRTCClient для работы с WebRTC.
 struct WebRTCClient {
-    peer_connection: RTCPeerConnection, // Соединение WebRTC.
+    // Arc, т.к. фоновая задача ICE-трикла (см. `spawn_remote_candidate_drain`) держит
+    // собственную ссылку на соединение и живет дольше, чем вызов connect/listen.
+    peer_connection: Arc<RTCPeerConnection>, // Соединение WebRTC.
     data_channel: Option<DataChannel>, // Канал передачи данных.
 }
 
-#[async_trait]
-impl Transport for WebRTCClient {
-    async fn connect(_addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
+impl WebRTCClient {
+    // `addr` — это адрес сигнального WebSocket-сервера: переиспользуем собственный
+    // `WebSocketClient` крейта вместо отдельного сигнального транспорта.
+    async fn connect(addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
         // Создаем API для WebRTC.
         let api = APIBuilder::new().build();
         let config = RTCConfiguration::default(); // Конфигурация по умолчанию.
-        let peer_connection = api.new_peer_connection(config).await?; // Создаем новое соединение.
+        let peer_connection = Arc::new(api.new_peer_connection(config).await?); // Создаем новое соединение.
 
         // Создаем канал данных.
         let data_channel = peer_connection.create_data_channel("data", None).await?;
 
+        let signaling = Arc::new(WebSocketClient::connect(addr).await?);
+        register_ice_trickle(&peer_connection, signaling.clone());
+
+        // Offer/answer: создаем предложение, публикуем его и ждем ответ по сигнальному сокету.
+        let offer = peer_connection.create_offer(None).await?;
+        peer_connection.set_local_description(offer.clone()).await?;
+        signaling
+            .send(&serde_json::to_vec(&SignalingMessage::Offer(offer))?)
+            .await?;
+
+        loop {
+            let frame = signaling.receive().await?;
+            match serde_json::from_slice(&frame)? {
+                SignalingMessage::Answer(answer) => {
+                    peer_connection.set_remote_description(answer).await?;
+                    break;
+                }
+                SignalingMessage::Candidate(candidate) => {
+                    peer_connection.add_ice_candidate(candidate).await?;
+                }
+                SignalingMessage::Offer(_) => continue, // Не ожидается на этой стороне, игнорируем.
+            }
+        }
+
+        // Кандидаты могут триклиться и после answer'а — держим сигнальный сокет
+        // открытым на все время жизни соединения вместо того, чтобы бросать его
+        // здесь же.
+        spawn_remote_candidate_drain(peer_connection.clone(), signaling);
+
+        Ok(WebRTCClient {
+            peer_connection,
+            data_channel: Some(data_channel),
+        })
+    }
+
+    // `addr` — адрес, на котором поднимается сигнальный WebSocket-сервер.
+    async fn listen(addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let api = APIBuilder::new().build();
+        let config = RTCConfiguration::default();
+        let peer_connection = Arc::new(api.new_peer_connection(config).await?);
+
+        // Принимающая сторона получает data channel через колбэк on_data_channel,
+        // а не создает его сама.
+        let (channel_tx, mut channel_rx) = tokio::sync::mpsc::channel(1);
+        peer_connection.on_data_channel(Box::new(move |channel: DataChannel| {
+            let channel_tx = channel_tx.clone();
+            Box::pin(async move {
+                let _ = channel_tx.send(channel).await;
+            })
+        }));
+
+        let signaling = Arc::new(WebSocketClient::listen(addr).await?);
+        register_ice_trickle(&peer_connection, signaling.clone());
+
+        // Ждем offer от сигнального сокета, отвечаем answer'ом.
+        let offer = loop {
+            let frame = signaling.receive().await?;
+            match serde_json::from_slice(&frame)? {
+                SignalingMessage::Offer(offer) => break offer,
+                SignalingMessage::Candidate(candidate) => {
+                    peer_connection.add_ice_candidate(candidate).await?;
+                }
+                SignalingMessage::Answer(_) => continue, // Не ожидается на этой стороне, игнорируем.
+            }
+        };
+        peer_connection.set_remote_description(offer).await?;
+        let answer = peer_connection.create_answer(None).await?;
+        peer_connection.set_local_description(answer.clone()).await?;
+        signaling
+            .send(&serde_json::to_vec(&SignalingMessage::Answer(answer))?)
+            .await?;
+
+        // Как и на стороне connect, продолжаем читать сигнальный сокет в фоне —
+        // удаленные кандидаты обычно продолжают приходить уже после answer'а.
+        spawn_remote_candidate_drain(peer_connection.clone(), signaling);
+
+        let data_channel = channel_rx
+            .recv()
+            .await
+            .ok_or("signaling closed before a data channel was negotiated")?;
+
         Ok(WebRTCClient {
             peer_connection,
             data_channel: Some(data_channel),
         })
     }
+}
+
+// Фоновая задача, которая продолжает читать сигнальный сокет уже после того, как
+// offer/answer согласованы: трикл ICE — это нормальный процесс, кандидаты могут
+// приходить с той стороны и после ответа, так что слушаем их на все время жизни
+// соединения, а не только в рамках хендшейк-цикла в connect/listen.
+fn spawn_remote_candidate_drain(peer_connection: Arc<RTCPeerConnection>, signaling: Arc<WebSocketClient>) {
+    tokio::spawn(async move {
+        loop {
+            // receive_message (в отличие от receive) явно отдает TransportMessage::Close
+            // на завершение потока, так что есть настоящее условие для выхода из цикла —
+            // plain receive() на закрытом сокете просто крутился бы вхолостую вечно.
+            let msg = match signaling.receive_message().await {
+                Ok(TransportMessage::Close) | Err(_) => break,
+                Ok(msg) => msg,
+            };
+            let frame = match msg {
+                TransportMessage::Binary(data) => data,
+                TransportMessage::Text(text) => text.into_bytes(),
+                _ => continue,
+            };
+            if let Ok(SignalingMessage::Candidate(candidate)) = serde_json::from_slice(&frame) {
+                let _ = peer_connection.add_ice_candidate(candidate).await;
+            }
+        }
+    });
+}
+
+// Регистрирует триклинг локальных ICE-кандидатов: каждый кандидат, который находит
+// ICE-агент, уходит в сигнальный сокет как `SignalingMessage::Candidate`.
+fn register_ice_trickle(peer_connection: &RTCPeerConnection, signaling: Arc<WebSocketClient>) {
+    peer_connection.on_ice_candidate(Box::new(move |candidate| {
+        let signaling = signaling.clone();
+        Box::pin(async move {
+            if let Some(candidate) = candidate {
+                if let Ok(init) = candidate.to_json() {
+                    let msg = SignalingMessage::Candidate(init);
+                    if let Ok(frame) = serde_json::to_vec(&msg) {
+                        let _ = signaling.send(&frame).await;
+                    }
+                }
+            }
+        })
+    }));
+}
 
+#[async_trait]
+impl Transport for WebRTCClient {
     async fn send(&self, data: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
         // Отправка данных через WebRTC.
         if let Some(channel) = &self.data_channel {
@@ -133,53 +620,471 @@ impl Transport for WebRTCClient {
         }
     }
 
-    async fn listen(_addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        // WebRTC не поддерживает серверное слушание соединений в прямом смысле.
-        Err("Listening is not supported for WebRTC".into())
+    async fn receive_message(&self) -> Result<TransportMessage, Box<dyn std::error::Error>> {
+        // DataChannel отдает либо бинарные, либо текстовые сообщения — маппим их напрямую.
+        if let Some(channel) = &self.data_channel {
+            let msg = channel.recv().await?;
+            return Ok(match msg {
+                DataChannelMessage::Binary(data) => TransportMessage::Binary(data),
+                DataChannelMessage::Text(text) => TransportMessage::Text(text),
+            });
+        }
+        Ok(TransportMessage::Close)
+    }
+
+    async fn close(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // Закрываем канал данных и соединение WebRTC.
+        if let Some(channel) = &self.data_channel {
+            channel.close().await?;
+        }
+        self.peer_connection.close().await?;
+        Ok(())
+    }
+}
+
+impl WebRTCClient {
+    // Представляет DataChannel как Stream+Sink (см. `Framed`).
+    fn into_framed(self) -> Framed {
+        shim_framed(self)
     }
 }
 
 // WebSocketClient для работы с WebSockets.
 struct WebSocketClient {
-    socket: Arc<RwLock<tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>>>, // Асинхронный WebSocket поток.
+    // Читающая и пишущая половины разнесены по отдельным мьютексам, чтобы heartbeat
+    // (которому нужна только пишущая половина) не блокировался на время, пока
+    // `receive`/`receive_message` ждут следующий фрейм через `read.next().await`.
+    read: Arc<AsyncMutex<SplitStream<Box<dyn WsIo>>>>,
+    write: Arc<AsyncMutex<SplitSink<Box<dyn WsIo>, Message>>>,
+    last_activity: Arc<Mutex<Instant>>, // Время последнего полученного фрейма (для keepalive).
 }
 
-#[async_trait]
-impl Transport for WebSocketClient {
+impl WebSocketClient {
     async fn connect(addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        // Подключаемся к WebSocket-серверу.
+        Self::connect_with(addr, WebSocketConfig::default(), TlsConfig::default()).await
+    }
+
+    async fn connect_with_config(
+        addr: &str,
+        config: WebSocketConfig,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::connect_with(addr, config, TlsConfig::default()).await
+    }
+
+    async fn connect_with(
+        addr: &str,
+        config: WebSocketConfig,
+        tls: TlsConfig,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        // Подключаемся к WebSocket-серверу. Для `wss://` передаем TLS-коннектор
+        // в `client_async_tls_with_connector`, как это делает async-tungstenite.
         let url = Url::parse(addr)?;
-        let (ws_stream, _) = connect_async(url).await?;
-        Ok(WebSocketClient {
-            socket: Arc::new(RwLock::new(ws_stream)),
-        })
+        let host = url.host_str().ok_or("ws url is missing a host")?;
+        let port = url
+            .port_or_known_default()
+            .ok_or("ws url is missing a port")?;
+        let tcp_stream = tokio::net::TcpStream::connect((host, port)).await?;
+
+        let connector = if url.scheme() == "wss" {
+            Some(Connector::Rustls(Arc::new(tls.client_rustls_config()?)))
+        } else {
+            None
+        };
+        let (ws_stream, _) =
+            tokio_tungstenite::client_async_tls_with_connector(url, tcp_stream, connector).await?;
+        Ok(Self::from_stream(Box::new(ws_stream), config))
+    }
+
+    async fn listen(addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::listen_with(addr, WebSocketConfig::default(), None).await
+    }
+
+    async fn listen_with_config(
+        addr: &str,
+        config: WebSocketConfig,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::listen_with(addr, config, None).await
+    }
+
+    async fn listen_with(
+        addr: &str,
+        config: WebSocketConfig,
+        tls: Option<TlsConfig>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        // Слушаем входящие соединения для WebSocket. Если передан `tls`, терминируем
+        // TLS на принятом сокете перед WebSocket-рукопожатием (для `wss://`).
+        let listener = TcpListener::bind(addr).await?;
+        println!("WebSocket server listening on {}", addr);
+        let (stream, _) = listener.accept().await?;
+        let boxed: Box<dyn WsIo> = match tls {
+            Some(tls) => {
+                let acceptor = TlsAcceptor::from(Arc::new(tls.server_rustls_config()?));
+                let tls_stream = acceptor.accept(stream).await?;
+                Box::new(accept_async(tls_stream).await?)
+            }
+            None => Box::new(accept_async(stream).await?),
+        };
+        Ok(Self::from_stream(boxed, config))
+    }
+
+    // Оборачивает уже установленный поток и запускает фоновый heartbeat:
+    // шлет Ping каждые `ping_interval` и закрывает соединение, если дольше
+    // `pong_timeout` не было никакой входящей активности (модель `hb: Instant` из actix-клиентов).
+    //
+    // Поток сразу разбивается на read/write половины (`StreamExt::split`), чтобы
+    // heartbeat мог слать Ping и закрывать write-половину независимо от того, что
+    // read-половина может быть надолго занята в `receive`/`receive_message`.
+    fn from_stream(stream: Box<dyn WsIo>, config: WebSocketConfig) -> Self {
+        let (sink, stream) = stream.split();
+        let read = Arc::new(AsyncMutex::new(stream));
+        let write = Arc::new(AsyncMutex::new(sink));
+        let last_activity = Arc::new(Mutex::new(Instant::now()));
+
+        // Heartbeat держит только слабую ссылку на write-половину: когда сброшены все ее
+        // владельцы (сам `WebSocketClient`, а после `into_framed` — полученный `Framed`),
+        // задача тихо останавливается вместо того чтобы держать соединение за собой навечно.
+        let hb_write: std::sync::Weak<AsyncMutex<SplitSink<Box<dyn WsIo>, Message>>> =
+            Arc::downgrade(&write);
+        let hb_last_activity = last_activity.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.ping_interval);
+            loop {
+                interval.tick().await;
+                let Some(write) = hb_write.upgrade() else {
+                    break; // Все владельцы write-половины сброшены.
+                };
+                let elapsed = hb_last_activity.lock().unwrap().elapsed();
+                if elapsed > config.pong_timeout {
+                    let mut write = write.lock().await;
+                    let _ = SinkExt::close(&mut *write).await;
+                    break;
+                }
+                let mut write = write.lock().await;
+                if write.send(Message::Ping(vec![])).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        WebSocketClient { read, write, last_activity }
     }
+}
 
+#[async_trait]
+impl Transport for WebSocketClient {
     async fn send(&self, data: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
         // Отправляем данные через WebSocket.
-        let mut socket = self.socket.write().await;
-        socket.send(Message::Binary(data.to_vec())).await?; // Отправка бинарных данных.
+        let mut write = self.write.lock().await;
+        write.send(Message::Binary(data.to_vec())).await?; // Отправка бинарных данных.
         Ok(())
     }
 
     async fn receive(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        // Получаем данные через WebSocket.
-        let mut socket = self.socket.write().await;
-        if let Some(Ok(msg)) = socket.next().await {
-            return Ok(msg.into_data());
+        // Один путь чтения на обе функции: receive_message уже продлевает keepalive
+        // и отвечает Pong на входящий Ping, так что receive разворачивает ее
+        // результат обратно в байты вместо того чтобы дублировать эту логику.
+        // Control-фреймы Ping/Pong пропускаются: их (обычно пустой) payload иначе
+        // был бы неотличим от пустого вектора, которым receive сообщает о закрытии.
+        loop {
+            match self.receive_message().await? {
+                TransportMessage::Text(text) => return Ok(text.into_bytes()),
+                TransportMessage::Binary(data) => return Ok(data),
+                TransportMessage::Ping(_) | TransportMessage::Pong(_) => continue,
+                TransportMessage::Close => return Ok(vec![]), // Соединение закрыто — пустой вектор как и раньше.
+            }
         }
-        Ok(vec![]) // Если данных нет, возвращаем пустой вектор.
     }
 
-    async fn listen(addr: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        // Слушаем входящие соединения для WebSocket.
-        let listener = TcpListener::bind(addr).await?;
-        println!("WebSocket server listening on {}", addr);
-        let (stream, _) = listener.accept().await?;
-        let ws_stream = accept_async(stream).await?;
-        Ok(WebSocketClient {
-            socket: Arc::new(RwLock::new(ws_stream)),
-        })
+    async fn receive_message(&self) -> Result<TransportMessage, Box<dyn std::error::Error>> {
+        // Получаем типизированное сообщение, попутно продлевая keepalive и
+        // автоматически отвечая Pong на входящий Ping.
+        let mut read = self.read.lock().await;
+        let next = read.next().await;
+        *self.last_activity.lock().unwrap() = Instant::now();
+        match next {
+            Some(Ok(Message::Text(text))) => Ok(TransportMessage::Text(text)),
+            Some(Ok(Message::Binary(data))) => Ok(TransportMessage::Binary(data)),
+            Some(Ok(Message::Ping(payload))) => {
+                self.write.lock().await.send(Message::Pong(payload.clone())).await?;
+                Ok(TransportMessage::Ping(payload))
+            }
+            Some(Ok(Message::Pong(payload))) => Ok(TransportMessage::Pong(payload)),
+            Some(Ok(Message::Close(_))) => Ok(TransportMessage::Close),
+            Some(Ok(Message::Frame(_))) => Ok(TransportMessage::Close),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(TransportMessage::Close),
+        }
+    }
+
+    async fn close(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // Закрываем WebSocket-соединение.
+        let mut write = self.write.lock().await;
+        SinkExt::close(&mut *write).await?;
+        Ok(())
+    }
+}
+
+impl WebSocketClient {
+    // Представляет соединение как Stream+Sink. Read/write половины уже разделены
+    // (см. `from_stream`), поэтому просто оборачиваем их общие `Arc` в `Framed` —
+    // никакого предположения о единоличном владении сокетом не требуется, так что
+    // это безопасно вызывать даже пока жив heartbeat (см. его `Weak`-ссылку выше).
+    fn into_framed(self) -> Framed {
+        let read = self.read;
+        let write = self.write;
+        let last_activity = self.last_activity;
+        let stream = futures_util::stream::unfold((read, last_activity), |(read, last_activity)| async move {
+            let item = read.lock().await.next().await?;
+            // Продлеваем keepalive так же, как receive_message, иначе heartbeat
+            // закрыл бы живое соединение через `pong_timeout`.
+            *last_activity.lock().unwrap() = Instant::now();
+            let item = item
+                .map(|msg| msg.into_data())
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>);
+            Some((item, (read, last_activity)))
+        });
+        let sink = futures_util::sink::unfold(write, |write, data: Vec<u8>| async move {
+            write
+                .lock()
+                .await
+                .send(Message::Binary(data))
+                .await
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            Ok::<_, Box<dyn std::error::Error>>(write)
+        });
+        Framed {
+            stream: Box::pin(stream),
+            sink: Box::pin(sink),
+        }
+    }
+}
+
+// Политика переподключения: стартовая задержка, множитель экспоненциального роста,
+// потолок задержки, необязательный лимит попыток и максимальный джиттер.
+#[derive(Debug, Clone)]
+struct ReconnectPolicy {
+    initial_delay: Duration,
+    multiplier: f64,
+    max_delay: Duration,
+    max_attempts: Option<u32>, // None — переподключаться бесконечно.
+    jitter: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy {
+            initial_delay: Duration::from_millis(200),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(30),
+            max_attempts: None,
+            jitter: Duration::from_millis(250),
+        }
+    }
+}
+
+// Состояние соединения, которое видит наблюдатель через callback `on_state_change`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ConnectionState {
+    Connected,
+    Reconnecting,
+    Failed,
+}
+
+type StateChangeCallback = Box<dyn Fn(ConnectionState) + Send + Sync>;
+
+// Обертка над любым транспортом из диспетчера схем (см. `connect`/`listen`), которая
+// прозрачно переподключается при ошибке send/receive: пересоздает соединение через
+// ту же схему-строку с экспоненциальным backoff + джиттером и буферизует кадры,
+// которые не удалось отправить, пока связь восстанавливается.
+struct ReconnectingTransport {
+    url: String,
+    policy: ReconnectPolicy,
+    inner: RwLock<Option<Box<dyn Transport>>>,
+    outbound_buffer: AsyncMutex<VecDeque<Vec<u8>>>,
+    buffer_capacity: usize,
+    on_state_change: Option<StateChangeCallback>,
+    // Серилизует попытки переподключения: несколько одновременных send/receive,
+    // заставших один и тот же обрыв связи, дозваниваются один раз и ждут друг друга
+    // вместо того чтобы каждый крутил свой собственный `connect(url)`.
+    reconnect_lock: AsyncMutex<()>,
+    // Растет на единицу при каждом успешном переподключении. Позволяет вызову
+    // `reconnect`, который ждал лока, понять: кто-то другой уже восстановил связь
+    // после того обрыва, из-за которого он сюда попал, и повторный дозвон не нужен.
+    generation: std::sync::atomic::AtomicU64,
+}
+
+impl ReconnectingTransport {
+    async fn connect(url: &str, policy: ReconnectPolicy) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::connect_with_callback(url, policy, None).await
+    }
+
+    async fn connect_with_callback(
+        url: &str,
+        policy: ReconnectPolicy,
+        on_state_change: Option<StateChangeCallback>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let transport = connect(url).await?;
+        let wrapper = ReconnectingTransport {
+            url: url.to_string(),
+            policy,
+            inner: RwLock::new(Some(transport)),
+            outbound_buffer: AsyncMutex::new(VecDeque::new()),
+            buffer_capacity: 256,
+            on_state_change,
+            reconnect_lock: AsyncMutex::new(()),
+            generation: std::sync::atomic::AtomicU64::new(0),
+        };
+        wrapper.notify(ConnectionState::Connected);
+        Ok(wrapper)
+    }
+
+    fn notify(&self, state: ConnectionState) {
+        if let Some(callback) = &self.on_state_change {
+            callback(state);
+        }
+    }
+
+    // Добавляет кадр в буфер неотправленных данных, отбрасывая самый старый при переполнении.
+    async fn buffer_outbound(&self, data: Vec<u8>) {
+        let mut buffer = self.outbound_buffer.lock().await;
+        if buffer.len() >= self.buffer_capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(data);
+    }
+
+    // Дослает все буферизованные кадры после успешного переподключения.
+    async fn flush_buffer(&self) {
+        let mut buffer = self.outbound_buffer.lock().await;
+        let inner_guard = self.inner.read().await;
+        if let Some(inner) = inner_guard.as_ref() {
+            while let Some(data) = buffer.pop_front() {
+                if inner.send(&data).await.is_err() {
+                    buffer.push_front(data);
+                    break;
+                }
+            }
+        }
+    }
+
+    // Пересоздает соединение по той же URL-строке через диспетчер схем, с
+    // экспоненциальным backoff и джиттером между попытками.
+    //
+    // `observed_generation` — значение `self.generation`, которое вызывающая сторона
+    // видела непосредственно перед тем, как ее send/receive провалился. Это позволяет
+    // отличить "связь действительно еще не восстановлена" от "пока я ждал лок, кто-то
+    // другой уже переподключился" — во втором случае повторный дозвон не нужен.
+    async fn reconnect(&self, observed_generation: u64) -> Result<(), Box<dyn std::error::Error>> {
+        let _guard = self.reconnect_lock.lock().await;
+        if self.generation.load(std::sync::atomic::Ordering::SeqCst) != observed_generation {
+            // Кто-то уже прошел через весь цикл переподключения ниже, пока мы ждали лок.
+            return Ok(());
+        }
+
+        *self.inner.write().await = None;
+        self.notify(ConnectionState::Reconnecting);
+
+        let mut attempt: u32 = 0;
+        let mut delay = self.policy.initial_delay;
+        loop {
+            if let Some(max_attempts) = self.policy.max_attempts {
+                if attempt >= max_attempts {
+                    self.notify(ConnectionState::Failed);
+                    return Err("exceeded max reconnect attempts".into());
+                }
+            }
+
+            match connect(&self.url).await {
+                Ok(transport) => {
+                    *self.inner.write().await = Some(transport);
+                    self.generation.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    self.notify(ConnectionState::Connected);
+                    self.flush_buffer().await;
+                    return Ok(());
+                }
+                Err(_) => {
+                    attempt += 1;
+                    let jitter_ms = if self.policy.jitter.is_zero() {
+                        0
+                    } else {
+                        rand::thread_rng().gen_range(0..=self.policy.jitter.as_millis() as u64)
+                    };
+                    tokio::time::sleep(delay + Duration::from_millis(jitter_ms)).await;
+                    let next_delay_ms =
+                        (delay.as_millis() as f64 * self.policy.multiplier).min(self.policy.max_delay.as_millis() as f64);
+                    delay = Duration::from_millis(next_delay_ms as u64);
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for ReconnectingTransport {
+    async fn send(&self, data: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+        let generation = self.generation.load(std::sync::atomic::Ordering::SeqCst);
+        let failed = {
+            let inner_guard = self.inner.read().await;
+            match inner_guard.as_ref() {
+                Some(inner) => inner.send(data).await.is_err(),
+                None => true,
+            }
+        };
+        if !failed {
+            return Ok(());
+        }
+        self.buffer_outbound(data.to_vec()).await;
+        self.reconnect(generation).await
+    }
+
+    async fn receive(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        loop {
+            let generation = self.generation.load(std::sync::atomic::Ordering::SeqCst);
+            let result = {
+                let inner_guard = self.inner.read().await;
+                match inner_guard.as_ref() {
+                    Some(inner) => inner.receive().await,
+                    None => Err("no active connection".into()),
+                }
+            };
+            match result {
+                // Пустой вектор от нижележащего транспорта, как правило, означает,
+                // что поток завершился (EOF/graceful close) — WebSocketClient::receive
+                // возвращает именно так на закрытии, а не Err, так что без этой ветки
+                // реконнект никогда не сработал бы на самое обычное отключение.
+                Ok(data) if data.is_empty() => self.reconnect(generation).await?,
+                Ok(data) => return Ok(data),
+                Err(_) => self.reconnect(generation).await?,
+            }
+        }
+    }
+
+    async fn receive_message(&self) -> Result<TransportMessage, Box<dyn std::error::Error>> {
+        loop {
+            let generation = self.generation.load(std::sync::atomic::Ordering::SeqCst);
+            let result = {
+                let inner_guard = self.inner.read().await;
+                match inner_guard.as_ref() {
+                    Some(inner) => inner.receive_message().await,
+                    None => Err("no active connection".into()),
+                }
+            };
+            match result {
+                // Ok(Close) — это graceful close/EOF нижележащего транспорта, а не
+                // ошибка, но он точно так же требует переподключения.
+                Ok(TransportMessage::Close) => self.reconnect(generation).await?,
+                Ok(msg) => return Ok(msg),
+                Err(_) => self.reconnect(generation).await?,
+            }
+        }
+    }
+
+    async fn close(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let inner_guard = self.inner.read().await;
+        if let Some(inner) = inner_guard.as_ref() {
+            inner.close().await?;
+        }
+        Ok(())
     }
 }
 
@@ -196,22 +1101,36 @@ async fn main() {
 
     #[cfg(not(target_arch = "wasm32"))]
     {
-        // Работаем с нативным приложением.
-        let client = QuinnClient::connect("127.0.0.1:4433").await.unwrap();
+        // Работаем с нативным приложением через диспетчер схем — тип транспорта
+        // определяется самой строкой подключения.
+        let client = connect("quic://127.0.0.1:4433").await.unwrap();
         client.send(b"Hello, Server!").await.unwrap();
         let response = client.receive().await.unwrap();
         println!("Received: {:?}", response);
+        client.close().await.unwrap();
 
         // WebSocket клиент.
-        let ws_client = WebSocketClient::connect("ws://127.0.0.1:8080").await.unwrap();
+        let ws_client = connect("ws://127.0.0.1:8080").await.unwrap();
         ws_client.send(b"Hello via WebSocket!").await.unwrap();
         let ws_response = ws_client.receive().await.unwrap();
         println!("Received via WebSocket: {:?}", ws_response);
+        ws_client.close().await.unwrap();
 
         // WebRTC клиент.
-        let webrtc_client = WebRTCClient::connect("wss://127.0.0.1:4433").await.unwrap();
+        let webrtc_client = connect("webrtc://127.0.0.1:4433").await.unwrap();
         webrtc_client.send(b"Hello via WebRTC!").await.unwrap();
         let webrtc_response = webrtc_client.receive().await.unwrap();
         println!("Received via WebRTC: {:?}", webrtc_response);
+        webrtc_client.close().await.unwrap();
+
+        // Долгоживущее соединение, которое само переподключается при обрыве.
+        let reconnecting = ReconnectingTransport::connect_with_callback(
+            "ws://127.0.0.1:8080",
+            ReconnectPolicy::default(),
+            Some(Box::new(|state| println!("connection state: {:?}", state))),
+        )
+        .await
+        .unwrap();
+        reconnecting.send(b"Hello via reconnecting transport!").await.unwrap();
     }
 }